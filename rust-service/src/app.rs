@@ -8,6 +8,8 @@ pub fn build_router(state: AppState) -> Router {
 
     Router::new()
         .route("/health", get(handlers::health))
+        .route("/health/live", get(handlers::health_live))
+        .route("/health/ready", get(handlers::health_ready))
         .route("/api/item/{id}", get(handlers::get_item))
         .route("/metrics", get(handlers::metrics_endpoint))
         // Logs HTTP automáticos (latencia, status, método, etc.)
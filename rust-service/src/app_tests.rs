@@ -25,6 +25,45 @@ async fn health_returns_ok() {
     assert_eq!(&bytes[..], b"ok");
 }
 
+#[tokio::test]
+async fn readiness_ok_against_sqlite() {
+    // `for_tests` usa el repo SQLite en memoria; `ping` corre `SELECT 1`.
+    let app = build_router(AppState::for_tests());
+
+    let res = app
+        .oneshot(
+            Request::builder()
+                .uri("/health/ready")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), axum::http::StatusCode::OK);
+}
+
+#[tokio::test]
+async fn readiness_503_while_draining() {
+    use std::sync::atomic::Ordering;
+
+    let state = AppState::for_tests();
+    state.draining.store(true, Ordering::Relaxed);
+    let app = build_router(state);
+
+    let res = app
+        .oneshot(
+            Request::builder()
+                .uri("/health/ready")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(res.status(), axum::http::StatusCode::SERVICE_UNAVAILABLE);
+}
+
 #[tokio::test]
 async fn item_route_exists() {
     let app = build_router(AppState::for_tests());
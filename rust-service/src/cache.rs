@@ -0,0 +1,38 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Caché en memoria de items (`id -> name`), invalidada por `NOTIFY`.
+///
+/// El consumidor del listener borra la entrada correspondiente cuando llega un
+/// `NOTIFY` cuyo payload es el id de la fila modificada; si el payload no es un
+/// id reconocible se vacía la caché entera por seguridad.
+#[derive(Default)]
+pub struct ItemCache {
+    entries: Mutex<HashMap<i32, String>>,
+}
+
+impl ItemCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Devuelve el nombre cacheado para `id`, si lo hay.
+    pub fn get(&self, id: i32) -> Option<String> {
+        self.entries.lock().unwrap().get(&id).cloned()
+    }
+
+    /// Guarda (o actualiza) la entrada de `id`.
+    pub fn put(&self, id: i32, name: String) {
+        self.entries.lock().unwrap().insert(id, name);
+    }
+
+    /// Invalida una única entrada.
+    pub fn invalidate(&self, id: i32) {
+        self.entries.lock().unwrap().remove(&id);
+    }
+
+    /// Vacía la caché completa.
+    pub fn clear(&self) {
+        self.entries.lock().unwrap().clear();
+    }
+}
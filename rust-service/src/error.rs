@@ -27,6 +27,11 @@ impl AppError {
     pub fn invalid_config(msg: &'static str) -> Self {
         Self::InvalidConfig(msg)
     }
+
+    /// `true` si el error envuelve un timeout al adquirir conexión del pool.
+    pub fn is_pool_timeout(&self) -> bool {
+        matches!(self, AppError::Db(sqlx::Error::PoolTimedOut))
+    }
 }
 
 impl IntoResponse for AppError {
@@ -1,20 +1,93 @@
-use crate::{error::AppError, state::AppState};
+use crate::{error::AppError, repository::ItemDto, state::AppState};
 use axum::{
     extract::{Path, State},
-    response::Response,
+    http::StatusCode,
+    response::{IntoResponse, Response},
     Json,
 };
 use serde::Serialize;
-use sqlx::Row;
+
+pub async fn health() -> &'static str {
+    "ok"
+}
+
+/// Liveness: sólo indica que el proceso está arriba; no toca dependencias.
+pub async fn health_live() -> Json<LiveBody> {
+    Json(LiveBody { status: "live" })
+}
+
+/// Readiness: comprueba que la DB es alcanzable (cacheado por `HEALTH_CACHE_MS`).
+pub async fn health_ready(State(st): State<AppState>) -> Response {
+    // Durante el drenado reportamos NOT READY para que el LB deje de enrutar.
+    if st.draining.load(std::sync::atomic::Ordering::Relaxed) {
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(ReadyBody {
+                status: "draining",
+                db_latency_ms: None,
+                reason: Some("shutting down"),
+                cached: false,
+                listener_connected: listener_state(&st),
+            }),
+        )
+            .into_response();
+    }
+
+    let acquire_timeout = st.db_acquire_timeout;
+    let r = st.health.readiness(&st.repo, acquire_timeout).await;
+
+    // Publica el contador de probes reales como gauge Prometheus.
+    st.metrics.set_health_probes(st.health.checks());
+
+    if r.ready {
+        (
+            StatusCode::OK,
+            Json(ReadyBody {
+                status: "ready",
+                db_latency_ms: r.db_latency_ms,
+                reason: None,
+                cached: r.cached,
+                listener_connected: listener_state(&st),
+            }),
+        )
+            .into_response()
+    } else {
+        (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(ReadyBody {
+                status: "error",
+                db_latency_ms: None,
+                reason: r.reason,
+                cached: r.cached,
+                listener_connected: listener_state(&st),
+            }),
+        )
+            .into_response()
+    }
+}
+
+/// Estado de la conexión del listener de `NOTIFY`, si está configurado.
+fn listener_state(st: &AppState) -> Option<bool> {
+    st.notifier.as_ref().map(|n| n.is_connected())
+}
 
 #[derive(Serialize)]
-pub struct ItemDto {
-    pub id: i32,
-    pub name: String,
+pub struct LiveBody {
+    status: &'static str,
 }
 
-pub async fn health() -> &'static str {
-    "ok"
+#[derive(Serialize)]
+pub struct ReadyBody {
+    status: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    db_latency_ms: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reason: Option<&'static str>,
+    /// `true` si el resultado se sirvió desde la caché sin re-probar la DB.
+    cached: bool,
+    /// Estado de la conexión del listener de `NOTIFY` (ausente si no hay listener).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    listener_connected: Option<bool>,
 }
 
 pub async fn get_item(
@@ -25,21 +98,32 @@ pub async fn get_item(
         return Err(AppError::invalid_config("id must be a positive integer"));
     }
 
-    let row_opt = sqlx::query(r#"SELECT id, name FROM items WHERE id = $1"#)
-        .bind(id)
-        .fetch_optional(&st.pool)
-        .await
-        .map_err(AppError::Db)?;
-
-    let row = match row_opt {
-        Some(r) => r,
-        None => return Err(AppError::NotFound(format!("Item {id} not found"))),
-    };
+    // Sólo usamos la caché cuando hay un listener que la invalida por NOTIFY;
+    // sin él (backend no-Postgres o sin `DB_LISTEN_CHANNELS`) serviríamos filas
+    // obsoletas indefinidamente, así que vamos directos a la DB como el baseline.
+    let cacheable = st.notifier.is_some();
 
-    let id: i32 = row.try_get("id").map_err(AppError::Db)?;
-    let name: String = row.try_get("name").map_err(AppError::Db)?;
+    if cacheable {
+        if let Some(name) = st.cache.get(id) {
+            return Ok(Json(ItemDto { id, name }));
+        }
+    }
 
-    Ok(Json(ItemDto { id, name }))
+    match st.repo.get_item(id).await {
+        Ok(Some(item)) => {
+            if cacheable {
+                st.cache.put(item.id, item.name.clone());
+            }
+            Ok(Json(item))
+        }
+        Ok(None) => Err(AppError::NotFound(format!("Item {id} not found"))),
+        Err(e) => {
+            if e.is_pool_timeout() {
+                st.metrics.inc_db_acquire_timeout();
+            }
+            Err(e)
+        }
+    }
 }
 
 pub async fn metrics_endpoint(State(st): State<AppState>) -> Response {
@@ -0,0 +1,94 @@
+use crate::repository::Repository;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Resultado de un chequeo de readiness.
+pub struct Readiness {
+    pub ready: bool,
+    pub db_latency_ms: Option<u64>,
+    pub reason: Option<&'static str>,
+    /// `true` si se devolvió el último resultado cacheado sin re-probar la DB.
+    pub cached: bool,
+}
+
+/// Cachea el último chequeo de dependencias para no martillear la DB.
+///
+/// Igual que el `health_count` de pict-rs, guardamos un timestamp y un contador
+/// de probes con atómicos; sólo se re-prueba cuando el resultado cacheado queda
+/// obsoleto respecto a `cache_window`.
+pub struct Health {
+    start: Instant,
+    cache_window: Duration,
+    last_ok_ms: AtomicU64,      // ms desde `start` del último probe OK (0 = nunca)
+    last_latency_ms: AtomicU64, // latencia del último probe OK
+    checks: AtomicU64,          // nº de probes reales ejecutados
+}
+
+impl Health {
+    pub fn new(cache_window: Duration) -> Self {
+        Self {
+            start: Instant::now(),
+            cache_window,
+            last_ok_ms: AtomicU64::new(0),
+            last_latency_ms: AtomicU64::new(0),
+            checks: AtomicU64::new(0),
+        }
+    }
+
+    /// Probes reales (no cacheados) ejecutados desde el arranque.
+    pub fn checks(&self) -> u64 {
+        self.checks.load(Ordering::Relaxed)
+    }
+
+    /// Adquiere una conexión y ejecuta `SELECT 1` bajo `acquire_timeout`,
+    /// devolviendo el resultado cacheado mientras siga fresco.
+    pub async fn readiness(
+        &self,
+        repo: &Arc<dyn Repository>,
+        acquire_timeout: Duration,
+    ) -> Readiness {
+        let now_ms = self.start.elapsed().as_millis() as u64;
+        let last_ok = self.last_ok_ms.load(Ordering::Relaxed);
+        let window_ms = self.cache_window.as_millis() as u64;
+
+        if last_ok != 0 && now_ms.saturating_sub(last_ok) < window_ms {
+            return Readiness {
+                ready: true,
+                db_latency_ms: Some(self.last_latency_ms.load(Ordering::Relaxed)),
+                reason: None,
+                cached: true,
+            };
+        }
+
+        self.checks.fetch_add(1, Ordering::Relaxed);
+
+        let probe_start = Instant::now();
+        match tokio::time::timeout(acquire_timeout, repo.ping()).await {
+            Ok(Ok(())) => {
+                let latency = probe_start.elapsed().as_millis() as u64;
+                self.last_ok_ms
+                    .store(self.start.elapsed().as_millis() as u64, Ordering::Relaxed);
+                self.last_latency_ms.store(latency, Ordering::Relaxed);
+                Readiness {
+                    ready: true,
+                    db_latency_ms: Some(latency),
+                    reason: None,
+                    cached: false,
+                }
+            }
+            Ok(Err(_)) => Readiness {
+                ready: false,
+                db_latency_ms: None,
+                reason: Some("database query failed"),
+                cached: false,
+            },
+            Err(_) => Readiness {
+                ready: false,
+                db_latency_ms: None,
+                reason: Some("database ping timed out"),
+                cached: false,
+            },
+        }
+    }
+}
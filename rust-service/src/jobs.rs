@@ -0,0 +1,452 @@
+use crate::error::AppError;
+use serde::Serialize;
+use sqlx::{PgPool, Row};
+use std::{sync::Arc, time::Duration};
+
+/// Estado de un job en la tabla `jobs`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JobStatus {
+    Queued,
+    Running,
+    Complete,
+    Failed,
+}
+
+impl JobStatus {
+    fn as_str(self) -> &'static str {
+        match self {
+            JobStatus::Queued => "queued",
+            JobStatus::Running => "running",
+            JobStatus::Complete => "complete",
+            JobStatus::Failed => "failed",
+        }
+    }
+}
+
+/// Un job reclamado por `pop`.
+#[derive(Debug, Clone)]
+pub struct Job {
+    pub id: i64,
+    pub kind: String,
+    pub payload: String,
+}
+
+/// Profundidad de la cola por estado.
+#[derive(Debug, Default, Serialize)]
+pub struct QueueDepth {
+    pub queued: i64,
+    pub running: i64,
+    pub complete: i64,
+    pub failed: i64,
+}
+
+/// Backend de la cola. En producción es siempre Postgres; el backend SQLite
+/// sólo existe para tests (la serie introdujo el repo SQLite en memoria) y usa
+/// SQL equivalente sin `FOR UPDATE SKIP LOCKED`, innecesario bajo el escritor
+/// único de SQLite.
+enum QueuePool {
+    Postgres(PgPool),
+    #[cfg(test)]
+    Sqlite(sqlx::SqlitePool),
+}
+
+/// Cola de trabajo durable respaldada por Postgres.
+///
+/// `pop` reclama un job en una única transacción con `FOR UPDATE SKIP LOCKED`,
+/// de modo que varios workers (o instancias) drenan la cola sin procesar el
+/// mismo job dos veces. Sigue el diseño de cola/`JobStatus` de pict-rs.
+pub struct QueueRepo {
+    pool: QueuePool,
+}
+
+impl QueueRepo {
+    pub fn new(pool: PgPool) -> Self {
+        Self {
+            pool: QueuePool::Postgres(pool),
+        }
+    }
+
+    /// Cola respaldada por SQLite en memoria, sólo para tests.
+    #[cfg(test)]
+    pub fn new_sqlite(pool: sqlx::SqlitePool) -> Self {
+        Self {
+            pool: QueuePool::Sqlite(pool),
+        }
+    }
+
+    /// Crea la tabla `jobs` si no existe (no hay infra de migraciones en el repo).
+    pub async fn ensure_schema(&self) -> Result<(), AppError> {
+        match &self.pool {
+            QueuePool::Postgres(pool) => {
+                sqlx::query(
+                    r#"
+                    CREATE TABLE IF NOT EXISTS jobs (
+                        id          BIGSERIAL PRIMARY KEY,
+                        kind        TEXT        NOT NULL,
+                        payload     TEXT        NOT NULL,
+                        status      TEXT        NOT NULL DEFAULT 'queued',
+                        run_at      TIMESTAMPTZ NOT NULL DEFAULT now(),
+                        started_at  TIMESTAMPTZ,
+                        created_at  TIMESTAMPTZ NOT NULL DEFAULT now()
+                    )
+                    "#,
+                )
+                .execute(pool)
+                .await
+                .map_err(AppError::Db)?;
+
+                sqlx::query(
+                    r#"CREATE INDEX IF NOT EXISTS jobs_status_run_at_idx ON jobs (status, run_at)"#,
+                )
+                .execute(pool)
+                .await
+                .map_err(AppError::Db)?;
+            }
+            #[cfg(test)]
+            QueuePool::Sqlite(pool) => {
+                sqlx::query(
+                    r#"
+                    CREATE TABLE IF NOT EXISTS jobs (
+                        id          INTEGER PRIMARY KEY AUTOINCREMENT,
+                        kind        TEXT NOT NULL,
+                        payload     TEXT NOT NULL,
+                        status      TEXT NOT NULL DEFAULT 'queued',
+                        run_at      TEXT NOT NULL DEFAULT (datetime('now')),
+                        started_at  TEXT,
+                        created_at  TEXT NOT NULL DEFAULT (datetime('now'))
+                    )
+                    "#,
+                )
+                .execute(pool)
+                .await
+                .map_err(AppError::Db)?;
+
+                sqlx::query(
+                    r#"CREATE INDEX IF NOT EXISTS jobs_status_run_at_idx ON jobs (status, run_at)"#,
+                )
+                .execute(pool)
+                .await
+                .map_err(AppError::Db)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Encola un job para ejecución inmediata.
+    pub async fn push(&self, kind: &str, payload: &str) -> Result<i64, AppError> {
+        let row = match &self.pool {
+            QueuePool::Postgres(pool) => {
+                sqlx::query(r#"INSERT INTO jobs (kind, payload) VALUES ($1, $2) RETURNING id"#)
+                    .bind(kind)
+                    .bind(payload)
+                    .fetch_one(pool)
+                    .await
+                    .map_err(AppError::Db)?
+                    .try_get::<i64, _>("id")
+            }
+            #[cfg(test)]
+            QueuePool::Sqlite(pool) => {
+                sqlx::query(r#"INSERT INTO jobs (kind, payload) VALUES (?1, ?2) RETURNING id"#)
+                    .bind(kind)
+                    .bind(payload)
+                    .fetch_one(pool)
+                    .await
+                    .map_err(AppError::Db)?
+                    .try_get::<i64, _>("id")
+            }
+        };
+        row.map_err(AppError::Db)
+    }
+
+    /// Reclama el siguiente job disponible, o `None` si la cola está vacía.
+    pub async fn pop(&self) -> Result<Option<Job>, AppError> {
+        let row_opt = match &self.pool {
+            QueuePool::Postgres(pool) => {
+                let mut tx = pool.begin().await.map_err(AppError::Db)?;
+                let row_opt = sqlx::query(
+                    r#"
+                    UPDATE jobs
+                       SET status = 'running', started_at = now()
+                     WHERE id = (
+                         SELECT id FROM jobs
+                          WHERE status = 'queued' AND run_at <= now()
+                          ORDER BY run_at
+                          FOR UPDATE SKIP LOCKED
+                          LIMIT 1
+                     )
+                    RETURNING id, kind, payload
+                    "#,
+                )
+                .fetch_optional(&mut *tx)
+                .await
+                .map_err(AppError::Db)?;
+                tx.commit().await.map_err(AppError::Db)?;
+                row_opt.map(|row| {
+                    Ok::<_, sqlx::Error>(Job {
+                        id: row.try_get("id")?,
+                        kind: row.try_get("kind")?,
+                        payload: row.try_get("payload")?,
+                    })
+                })
+            }
+            #[cfg(test)]
+            QueuePool::Sqlite(pool) => sqlx::query(
+                r#"
+                UPDATE jobs
+                   SET status = 'running', started_at = datetime('now')
+                 WHERE id = (
+                     SELECT id FROM jobs
+                      WHERE status = 'queued' AND run_at <= datetime('now')
+                      ORDER BY run_at
+                      LIMIT 1
+                 )
+                RETURNING id, kind, payload
+                "#,
+            )
+            .fetch_optional(pool)
+            .await
+            .map_err(AppError::Db)?
+            .map(|row| {
+                Ok::<_, sqlx::Error>(Job {
+                    id: row.try_get("id")?,
+                    kind: row.try_get("kind")?,
+                    payload: row.try_get("payload")?,
+                })
+            }),
+        };
+
+        match row_opt {
+            Some(job) => Ok(Some(job.map_err(AppError::Db)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Marca un job como completado.
+    pub async fn complete(&self, id: i64) -> Result<(), AppError> {
+        self.set_status(id, JobStatus::Complete).await
+    }
+
+    /// Marca un job como fallido.
+    pub async fn fail(&self, id: i64) -> Result<(), AppError> {
+        self.set_status(id, JobStatus::Failed).await
+    }
+
+    async fn set_status(&self, id: i64, status: JobStatus) -> Result<(), AppError> {
+        match &self.pool {
+            QueuePool::Postgres(pool) => {
+                sqlx::query(r#"UPDATE jobs SET status = $1 WHERE id = $2"#)
+                    .bind(status.as_str())
+                    .bind(id)
+                    .execute(pool)
+                    .await
+                    .map_err(AppError::Db)?;
+            }
+            #[cfg(test)]
+            QueuePool::Sqlite(pool) => {
+                sqlx::query(r#"UPDATE jobs SET status = ?1 WHERE id = ?2"#)
+                    .bind(status.as_str())
+                    .bind(id)
+                    .execute(pool)
+                    .await
+                    .map_err(AppError::Db)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Devuelve jobs `running` cuyo `started_at` superó el heartbeat al estado
+    /// `queued` para que otro worker los reintente.
+    pub async fn reap(&self, heartbeat_timeout: Duration) -> Result<u64, AppError> {
+        let secs = heartbeat_timeout.as_secs() as i64;
+        let result = match &self.pool {
+            QueuePool::Postgres(pool) => sqlx::query(
+                r#"
+                UPDATE jobs
+                   SET status = 'queued', started_at = NULL
+                 WHERE status = 'running'
+                   AND started_at < now() - make_interval(secs => $1)
+                "#,
+            )
+            .bind(secs)
+            .execute(pool)
+            .await
+            .map_err(AppError::Db)?,
+            #[cfg(test)]
+            QueuePool::Sqlite(pool) => sqlx::query(
+                r#"
+                UPDATE jobs
+                   SET status = 'queued', started_at = NULL
+                 WHERE status = 'running'
+                   AND started_at < datetime('now', ?1)
+                "#,
+            )
+            .bind(format!("-{secs} seconds"))
+            .execute(pool)
+            .await
+            .map_err(AppError::Db)?,
+        };
+
+        Ok(result.rows_affected())
+    }
+
+    /// Profundidad de la cola por estado.
+    pub async fn depth(&self) -> Result<QueueDepth, AppError> {
+        const SQL: &str = r#"SELECT status, count(*) AS n FROM jobs GROUP BY status"#;
+
+        let mut depth = QueueDepth::default();
+        let rows: Vec<(String, i64)> = match &self.pool {
+            QueuePool::Postgres(pool) => sqlx::query(SQL)
+                .fetch_all(pool)
+                .await
+                .map_err(AppError::Db)?
+                .into_iter()
+                .map(|row| Ok((row.try_get("status")?, row.try_get("n")?)))
+                .collect::<Result<_, sqlx::Error>>()
+                .map_err(AppError::Db)?,
+            #[cfg(test)]
+            QueuePool::Sqlite(pool) => sqlx::query(SQL)
+                .fetch_all(pool)
+                .await
+                .map_err(AppError::Db)?
+                .into_iter()
+                .map(|row| Ok((row.try_get("status")?, row.try_get("n")?)))
+                .collect::<Result<_, sqlx::Error>>()
+                .map_err(AppError::Db)?,
+        };
+
+        for (status, n) in rows {
+            match status.as_str() {
+                "queued" => depth.queued = n,
+                "running" => depth.running = n,
+                "complete" => depth.complete = n,
+                "failed" => depth.failed = n,
+                _ => {}
+            }
+        }
+        Ok(depth)
+    }
+}
+
+/// Intervalo de poll cuando la cola está vacía.
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Lanza el pool de workers y el reaper, devolviendo el handle compartido.
+pub fn spawn(pool: PgPool, workers: u32, heartbeat_timeout: Duration) -> Arc<QueueRepo> {
+    let queue = Arc::new(QueueRepo::new(pool));
+
+    {
+        let queue = queue.clone();
+        tokio::spawn(async move {
+            if let Err(e) = queue.ensure_schema().await {
+                tracing::error!(error = ?e, "failed to ensure jobs schema; workers disabled");
+                return;
+            }
+
+            for id in 0..workers {
+                tokio::spawn(worker_loop(queue.clone(), id));
+            }
+            tokio::spawn(reaper_loop(queue.clone(), heartbeat_timeout));
+        });
+    }
+
+    queue
+}
+
+async fn worker_loop(queue: Arc<QueueRepo>, worker_id: u32) {
+    loop {
+        match queue.pop().await {
+            Ok(Some(job)) => {
+                let id = job.id;
+                match run_job(&job).await {
+                    Ok(()) => {
+                        if let Err(e) = queue.complete(id).await {
+                            tracing::error!(job_id = id, error = ?e, "failed to mark job complete");
+                        }
+                    }
+                    Err(e) => {
+                        tracing::warn!(job_id = id, error = %e, "job failed");
+                        if let Err(e) = queue.fail(id).await {
+                            tracing::error!(job_id = id, error = ?e, "failed to mark job failed");
+                        }
+                    }
+                }
+            }
+            Ok(None) => tokio::time::sleep(POLL_INTERVAL).await,
+            Err(e) => {
+                tracing::warn!(worker_id, error = ?e, "pop failed; backing off");
+                tokio::time::sleep(POLL_INTERVAL).await;
+            }
+        }
+    }
+}
+
+async fn reaper_loop(queue: Arc<QueueRepo>, heartbeat_timeout: Duration) {
+    loop {
+        tokio::time::sleep(heartbeat_timeout).await;
+        match queue.reap(heartbeat_timeout).await {
+            Ok(n) if n > 0 => tracing::warn!(requeued = n, "reaped stuck jobs"),
+            Ok(_) => {}
+            Err(e) => tracing::error!(error = ?e, "reaper failed"),
+        }
+    }
+}
+
+/// Ejecutor de jobs. De momento sólo traza; los kinds concretos (emails,
+/// webhooks, …) se añadirán despachando sobre `job.kind`.
+async fn run_job(job: &Job) -> Result<(), String> {
+    tracing::info!(job_id = job.id, kind = %job.kind, "processing job");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    async fn sqlite_queue() -> QueueRepo {
+        use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+
+        let opts = SqliteConnectOptions::from_str("sqlite::memory:")
+            .expect("parse sqlite options")
+            .shared_cache(true);
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect_with(opts)
+            .await
+            .expect("connect sqlite");
+
+        let queue = QueueRepo::new_sqlite(pool);
+        queue.ensure_schema().await.expect("ensure schema");
+        queue
+    }
+
+    #[tokio::test]
+    async fn push_pop_complete_round_trip() {
+        let queue = sqlite_queue().await;
+
+        let id = queue.push("email", "{\"to\":\"a@b.c\"}").await.unwrap();
+        assert!(id > 0);
+
+        let job = queue.pop().await.unwrap().expect("a queued job");
+        assert_eq!(job.id, id);
+        assert_eq!(job.kind, "email");
+        assert_eq!(job.payload, "{\"to\":\"a@b.c\"}");
+
+        // Reclamado: la cola queda vacía para un segundo pop.
+        assert!(queue.pop().await.unwrap().is_none());
+
+        queue.complete(id).await.unwrap();
+
+        let depth = queue.depth().await.unwrap();
+        assert_eq!(depth.queued, 0);
+        assert_eq!(depth.running, 0);
+        assert_eq!(depth.complete, 1);
+    }
+
+    #[tokio::test]
+    async fn pop_on_empty_queue_returns_none() {
+        let queue = sqlite_queue().await;
+        assert!(queue.pop().await.unwrap().is_none());
+    }
+}
@@ -1,10 +1,20 @@
 mod app;
+mod cache;
 mod error;
 mod handlers;
+mod health;
+mod jobs;
 mod metrics;
+mod notify;
+mod repository;
 mod state;
 
+use crate::metrics::Metrics;
+use crate::repository::Repository;
 use crate::state::{AppState, Config};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
 use tracing_subscriber::{fmt, EnvFilter};
 
 #[tokio::main]
@@ -26,6 +36,13 @@ async fn main() -> Result<(), error::AppError> {
     );
 
     let state = AppState::new(&cfg).await?;
+
+    // Handles necesarios para el drenado tras recibir la señal de shutdown.
+    let metrics = state.metrics.clone();
+    let draining = state.draining.clone();
+    let repo = state.repo.clone();
+    let shutdown_timeout = cfg.shutdown_timeout;
+
     let router = app::build_router(state);
 
     let addr = format!("0.0.0.0:{}", cfg.port);
@@ -36,12 +53,69 @@ async fn main() -> Result<(), error::AppError> {
         .map_err(error::AppError::Io)?;
 
     axum::serve(listener, router)
+        .with_graceful_shutdown(shutdown_signal(metrics, draining, shutdown_timeout))
         .await
         .map_err(error::AppError::Io)?;
 
+    // El servidor dejó de aceptar conexiones: drenamos el pool limpiamente.
+    tracing::info!("draining database pool");
+    repo.close().await;
+
     Ok(())
 }
 
+/// Espera SIGTERM/SIGINT y luego drena las requests en vuelo.
+///
+/// Al recibir la señal marca el proceso como `draining` (el readiness pasa a
+/// 503 para que el balanceador deje de enrutar) y espera a que el gauge
+/// `http_requests_in_progress_total` llegue a cero, acotado por `SHUTDOWN_TIMEOUT_SECS`.
+async fn shutdown_signal(
+    metrics: Arc<Metrics>,
+    draining: Arc<AtomicBool>,
+    timeout: Duration,
+) {
+    let ctrl_c = async {
+        tokio::signal::ctrl_c()
+            .await
+            .expect("failed to install Ctrl+C handler");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("failed to install SIGTERM handler")
+            .recv()
+            .await;
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {}
+        _ = terminate => {}
+    }
+
+    tracing::info!("shutdown signal received; draining in-flight requests");
+    draining.store(true, Ordering::Relaxed);
+
+    let drained = tokio::time::timeout(timeout, async {
+        while metrics.in_flight_total() > 0 {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+    })
+    .await;
+
+    match drained {
+        Ok(()) => tracing::info!("all in-flight requests completed"),
+        Err(_) => tracing::warn!(
+            timeout_secs = timeout.as_secs(),
+            in_flight = metrics.in_flight_total(),
+            "shutdown timeout reached with requests still in flight"
+        ),
+    }
+}
+
 #[cfg(test)]
 mod app_tests;
 
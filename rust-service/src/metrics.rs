@@ -6,7 +6,8 @@ use axum::{
     response::Response,
 };
 use prometheus::{
-    Encoder, HistogramOpts, HistogramVec, IntCounterVec, IntGaugeVec, Opts, Registry, TextEncoder,
+    Encoder, HistogramOpts, HistogramVec, IntCounter, IntCounterVec, IntGauge, IntGaugeVec, Opts,
+    Registry, TextEncoder,
 };
 use std::time::Instant;
 
@@ -15,6 +16,19 @@ pub struct Metrics {
     req_total: IntCounterVec,
     req_duration: HistogramVec,
     in_flight: IntGaugeVec,
+    in_flight_total: IntGauge,
+
+    // Instrumentación del pool de conexiones.
+    db_pool_size: IntGauge,
+    db_pool_idle: IntGauge,
+    db_pool_in_use: IntGauge,
+    db_acquire_timeouts: IntCounter,
+
+    // Chequeos de readiness.
+    health_probes: IntGauge,
+
+    // Profundidad de la cola de jobs por estado.
+    job_queue_depth: IntGaugeVec,
 }
 
 impl Metrics {
@@ -51,15 +65,72 @@ impl Metrics {
         )
         .expect("gauge");
 
+        let in_flight_total = IntGauge::new(
+            "http_requests_in_progress_total",
+            "HTTP requests currently in progress across all paths",
+        )
+        .expect("gauge");
+
+        let db_pool_size = IntGauge::new(
+            "db_pool_connections",
+            "Current size of the sqlx connection pool",
+        )
+        .expect("gauge");
+        let db_pool_idle = IntGauge::new(
+            "db_pool_connections_idle",
+            "Idle connections in the sqlx pool",
+        )
+        .expect("gauge");
+        let db_pool_in_use = IntGauge::new(
+            "db_pool_connections_in_use",
+            "Connections currently checked out of the sqlx pool",
+        )
+        .expect("gauge");
+        let db_acquire_timeouts = IntCounter::new(
+            "db_pool_acquire_timeouts_total",
+            "Total pool acquire-timeout failures",
+        )
+        .expect("counter");
+        let health_probes = IntGauge::new(
+            "health_readiness_probes_total",
+            "Real (non-cached) readiness probes executed against the DB",
+        )
+        .expect("gauge");
+        let job_queue_depth = IntGaugeVec::new(
+            Opts::new("job_queue_depth", "Jobs in the durable queue by status"),
+            &["status"],
+        )
+        .expect("gauge");
+
         registry.register(Box::new(req_total.clone())).unwrap();
         registry.register(Box::new(req_duration.clone())).unwrap();
         registry.register(Box::new(in_flight.clone())).unwrap();
+        registry
+            .register(Box::new(in_flight_total.clone()))
+            .unwrap();
+        registry.register(Box::new(db_pool_size.clone())).unwrap();
+        registry.register(Box::new(db_pool_idle.clone())).unwrap();
+        registry.register(Box::new(db_pool_in_use.clone())).unwrap();
+        registry
+            .register(Box::new(db_acquire_timeouts.clone()))
+            .unwrap();
+        registry.register(Box::new(health_probes.clone())).unwrap();
+        registry
+            .register(Box::new(job_queue_depth.clone()))
+            .unwrap();
 
         Self {
             registry,
             req_total,
             req_duration,
             in_flight,
+            in_flight_total,
+            db_pool_size,
+            db_pool_idle,
+            db_pool_in_use,
+            db_acquire_timeouts,
+            health_probes,
+            job_queue_depth,
         }
     }
 
@@ -81,12 +152,52 @@ impl Metrics {
             .observe(seconds);
     }
 
+    /// Actualiza los gauges del pool a partir de una instantánea de sqlx.
+    pub fn set_pool_stats(&self, size: u32, idle: usize) {
+        self.db_pool_size.set(size as i64);
+        self.db_pool_idle.set(idle as i64);
+        self.db_pool_in_use.set(size as i64 - idle as i64);
+    }
+
+    /// Incrementa el contador de fallos por timeout al adquirir del pool.
+    pub fn inc_db_acquire_timeout(&self) {
+        self.db_acquire_timeouts.inc();
+    }
+
+    /// Publica el nº de probes reales de readiness ejecutados desde el arranque.
+    pub fn set_health_probes(&self, probes: u64) {
+        self.health_probes.set(probes as i64);
+    }
+
+    /// Publica la profundidad de la cola de jobs por estado.
+    pub fn set_queue_depth(&self, depth: &crate::jobs::QueueDepth) {
+        self.job_queue_depth
+            .with_label_values(&["queued"])
+            .set(depth.queued);
+        self.job_queue_depth
+            .with_label_values(&["running"])
+            .set(depth.running);
+        self.job_queue_depth
+            .with_label_values(&["complete"])
+            .set(depth.complete);
+        self.job_queue_depth
+            .with_label_values(&["failed"])
+            .set(depth.failed);
+    }
+
     pub fn inc_in_flight(&self, path: &str) {
         self.in_flight.with_label_values(&[path]).inc();
+        self.in_flight_total.inc();
     }
 
     pub fn dec_in_flight(&self, path: &str) {
         self.in_flight.with_label_values(&[path]).dec();
+        self.in_flight_total.dec();
+    }
+
+    /// Total de requests HTTP en curso; usado por el drenado en shutdown.
+    pub fn in_flight_total(&self) -> i64 {
+        self.in_flight_total.get()
     }
 
     pub fn render(&self) -> (String, Vec<u8>) {
@@ -120,7 +231,7 @@ pub async fn metrics_middleware(
     let path = Metrics::path_label(&req);
 
     // optional: do not measure /metrics or /health to avoid contaminating RPS/latency
-    if path == "/metrics" || path == "/health" {
+    if path == "/metrics" || path == "/health" || path.starts_with("/health/") {
         return next.run(req).await;
     }
 
@@ -0,0 +1,137 @@
+use sqlx::postgres::PgListener;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::sync::broadcast;
+
+/// Un `NOTIFY` recibido del servidor Postgres.
+#[derive(Clone, Debug)]
+pub struct Notification {
+    pub channel: String,
+    pub payload: String,
+}
+
+/// Escucha `NOTIFY` en una conexión dedicada (separada del pool de requests) y
+/// reenvía cada `(channel, payload)` a todos los suscriptores vía `broadcast`.
+///
+/// La tarea de fondo se reconecta con backoff si la conexión dedicada cae y
+/// vuelve a emitir todos los `LISTEN` tras reconectar.
+pub struct Notifier {
+    tx: broadcast::Sender<Notification>,
+    channels: Vec<String>,
+    /// Estado actual de la conexión dedicada del listener (útil para readiness).
+    connected: Arc<AtomicBool>,
+}
+
+/// Capacidad del canal broadcast; si un suscriptor se atrasa recibirá `Lagged`.
+const BROADCAST_CAPACITY: usize = 256;
+
+/// Backoff inicial entre intentos de reconexión del listener.
+const INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+
+/// Backoff máximo entre intentos de reconexión del listener.
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Tiempo mínimo que una conexión debe mantenerse viva para considerarla sana y
+/// resetear el backoff; evita bucles apretados ante flaps de conexión.
+const MIN_HEALTHY: Duration = Duration::from_secs(30);
+
+impl Notifier {
+    /// Arranca la tarea de fondo y devuelve el `Notifier` compartido.
+    pub fn spawn(database_url: String, channels: Vec<String>) -> Arc<Self> {
+        let (tx, _rx) = broadcast::channel(BROADCAST_CAPACITY);
+        let connected = Arc::new(AtomicBool::new(false));
+
+        let notifier = Arc::new(Self {
+            tx: tx.clone(),
+            channels: channels.clone(),
+            connected: connected.clone(),
+        });
+
+        tokio::spawn(listen_loop(database_url, channels, tx, connected));
+
+        notifier
+    }
+
+    /// Suscribe un receptor nuevo al flujo de notificaciones.
+    pub fn subscribe(&self) -> broadcast::Receiver<Notification> {
+        self.tx.subscribe()
+    }
+
+    /// Canales en los que se hizo `LISTEN`.
+    pub fn channels(&self) -> &[String] {
+        &self.channels
+    }
+
+    /// `true` si la conexión dedicada del listener está establecida ahora mismo.
+    pub fn is_connected(&self) -> bool {
+        self.connected.load(Ordering::Relaxed)
+    }
+}
+
+async fn listen_loop(
+    database_url: String,
+    channels: Vec<String>,
+    tx: broadcast::Sender<Notification>,
+    connected: Arc<AtomicBool>,
+) {
+    let mut backoff = INITIAL_BACKOFF;
+
+    loop {
+        match connect_and_listen(&database_url, &channels).await {
+            Ok(mut listener) => {
+                connected.store(true, Ordering::Relaxed);
+                let up_since = Instant::now();
+                if let Err(e) = pump(&mut listener, &tx).await {
+                    tracing::warn!(error = %e, "notification listener dropped; reconnecting with backoff");
+                }
+                connected.store(false, Ordering::Relaxed);
+
+                // Sólo reseteamos el backoff si la conexión se mantuvo sana un
+                // mínimo: un flap que conecta y cae al instante (auth/replication)
+                // no debe colapsar el backoff exponencial a un bucle apretado.
+                if up_since.elapsed() >= MIN_HEALTHY {
+                    backoff = INITIAL_BACKOFF;
+                }
+            }
+            Err(e) => {
+                tracing::warn!(error = %e, "notification listener connect failed; reconnecting with backoff");
+            }
+        }
+
+        tokio::time::sleep(backoff).await;
+        backoff = (backoff * 2).min(MAX_BACKOFF);
+    }
+}
+
+/// Abre la conexión dedicada y reemite todos los `LISTEN`.
+async fn connect_and_listen(
+    database_url: &str,
+    channels: &[String],
+) -> Result<PgListener, sqlx::Error> {
+    let mut listener = PgListener::connect(database_url).await?;
+
+    let refs: Vec<&str> = channels.iter().map(String::as_str).collect();
+    listener.listen_all(refs).await?;
+    tracing::info!(channels = ?channels, "LISTEN established on dedicated connection");
+
+    Ok(listener)
+}
+
+/// Bombea notificaciones hasta que la conexión falle.
+async fn pump(
+    listener: &mut PgListener,
+    tx: &broadcast::Sender<Notification>,
+) -> Result<(), sqlx::Error> {
+    loop {
+        let notification = listener.recv().await?;
+        let msg = Notification {
+            channel: notification.channel().to_string(),
+            payload: notification.payload().to_string(),
+        };
+        // Un error de send sólo significa que no hay suscriptores; no es fatal.
+        let _ = tx.send(msg);
+    }
+}
@@ -0,0 +1,322 @@
+use crate::{error::AppError, state::Config};
+use async_trait::async_trait;
+use serde::Serialize;
+
+use std::{str::FromStr, sync::Arc, time::Duration};
+
+#[derive(Serialize)]
+pub struct ItemDto {
+    pub id: i32,
+    pub name: String,
+}
+
+/// Abstracción del acceso a datos.
+///
+/// Cada backend (Postgres, SQLite, MySQL) implementa este trait; `AppState`
+/// guarda un `Arc<dyn Repository>` y los handlers no conocen el motor concreto.
+#[async_trait]
+pub trait Repository: Send + Sync {
+    async fn get_item(&self, id: i32) -> Result<Option<ItemDto>, AppError>;
+
+    /// Comprueba que una conexión del pool está viva (`SELECT 1`).
+    async fn ping(&self) -> Result<(), AppError>;
+
+    /// Cierra el pool, drenando sus conexiones (usado en shutdown).
+    async fn close(&self);
+
+    /// Devuelve el pool Postgres subyacente, si el backend es Postgres.
+    ///
+    /// Sirve de punto de extensión para funcionalidades específicas de Postgres;
+    /// hoy lo usa la cola de jobs con `SKIP LOCKED`. Las métricas del pool y el
+    /// drenado en shutdown van por `pool_stats` y `close`, comunes a todo backend.
+    fn pg_pool(&self) -> Option<sqlx::PgPool> {
+        None
+    }
+
+    /// Estadísticas del pool de conexiones, para exponerlas como métricas.
+    fn pool_stats(&self) -> Option<PoolStats> {
+        None
+    }
+}
+
+/// Instantánea del pool: tamaño actual y conexiones ociosas.
+#[derive(Debug, Clone, Copy)]
+pub struct PoolStats {
+    pub size: u32,
+    pub idle: usize,
+}
+
+/// Selecciona el backend a partir del esquema de `DATABASE_URL`.
+pub async fn connect(cfg: &Config) -> Result<Arc<dyn Repository>, AppError> {
+    let url = cfg.database_url.as_str();
+
+    if url.starts_with("postgres://") || url.starts_with("postgresql://") {
+        Ok(Arc::new(PostgresRepo::connect(cfg).await?))
+    } else if url.starts_with("sqlite:") {
+        Ok(Arc::new(SqliteRepo::connect(cfg).await?))
+    } else if url.starts_with("mysql://") {
+        Ok(Arc::new(MySqlRepo::connect(cfg).await?))
+    } else {
+        Err(AppError::invalid_config(
+            "DATABASE_URL scheme must be one of postgres://, sqlite://, mysql://",
+        ))
+    }
+}
+
+/// Aplica el timeout de arranque (startup) al futuro que crea el pool.
+///
+/// Lo más compatible es un `tokio::time::timeout` externo sobre el
+/// `connect_with`; lo comparten los tres backends.
+async fn with_connect_timeout<F, P>(cfg: &Config, fut: F) -> Result<P, AppError>
+where
+    F: std::future::Future<Output = Result<P, sqlx::Error>>,
+{
+    tokio::time::timeout(cfg.db_connect_timeout, fut)
+        .await
+        .map_err(|_| AppError::invalid_config("DB connection timed out while creating pool"))?
+        .map_err(AppError::Db)
+}
+
+pub struct PostgresRepo {
+    pub pool: sqlx::PgPool,
+}
+
+impl PostgresRepo {
+    pub async fn connect(cfg: &Config) -> Result<Self, AppError> {
+        use sqlx::postgres::{PgConnectOptions, PgPoolOptions, PgSslMode};
+
+        let mut connect_opts = PgConnectOptions::from_str(&cfg.database_url).map_err(|_| {
+            AppError::invalid_config(
+                "DATABASE_URL is not a valid Postgres connection string (PgConnectOptions parse failed)",
+            )
+        })?;
+
+        // TLS: aplica sslmode, CA y (opcional) certificado de cliente.
+        if let Some(mode) = cfg.db_sslmode.as_deref() {
+            let mode = match mode {
+                "disable" => PgSslMode::Disable,
+                "require" => PgSslMode::Require,
+                "verify-ca" => PgSslMode::VerifyCa,
+                "verify-full" => PgSslMode::VerifyFull,
+                // Validado en Config::from_env.
+                _ => unreachable!("invalid DB_SSLMODE passed config validation"),
+            };
+            connect_opts = connect_opts.ssl_mode(mode);
+        }
+        if let Some(root_cert) = cfg.db_ssl_root_cert.as_deref() {
+            connect_opts = connect_opts.ssl_root_cert(root_cert);
+        }
+        if let (Some(cert), Some(key)) =
+            (cfg.db_ssl_client_cert.as_deref(), cfg.db_ssl_client_key.as_deref())
+        {
+            connect_opts = connect_opts.ssl_client_cert(cert).ssl_client_key(key);
+        }
+
+        let fut = PgPoolOptions::new()
+            .max_connections(cfg.pool_max_connections)
+            .min_connections(cfg.pool_min_connections)
+            .acquire_timeout(cfg.db_acquire_timeout)
+            .idle_timeout(Duration::from_secs(30))
+            .max_lifetime(Duration::from_secs(300))
+            .connect_with(connect_opts);
+
+        let pool = with_connect_timeout(cfg, fut).await?;
+        Ok(Self { pool })
+    }
+}
+
+#[async_trait]
+impl Repository for PostgresRepo {
+    async fn get_item(&self, id: i32) -> Result<Option<ItemDto>, AppError> {
+        use sqlx::Row;
+
+        let row_opt = sqlx::query(r#"SELECT id, name FROM items WHERE id = $1"#)
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(AppError::Db)?;
+
+        match row_opt {
+            Some(row) => {
+                let id: i32 = row.try_get("id").map_err(AppError::Db)?;
+                let name: String = row.try_get("name").map_err(AppError::Db)?;
+                Ok(Some(ItemDto { id, name }))
+            }
+            None => Ok(None),
+        }
+    }
+
+    async fn ping(&self) -> Result<(), AppError> {
+        sqlx::query("SELECT 1")
+            .execute(&self.pool)
+            .await
+            .map_err(AppError::Db)?;
+        Ok(())
+    }
+
+    fn pg_pool(&self) -> Option<sqlx::PgPool> {
+        Some(self.pool.clone())
+    }
+
+    fn pool_stats(&self) -> Option<PoolStats> {
+        Some(PoolStats {
+            size: self.pool.size(),
+            idle: self.pool.num_idle(),
+        })
+    }
+
+    async fn close(&self) {
+        self.pool.close().await;
+    }
+}
+
+pub struct SqliteRepo {
+    pub pool: sqlx::SqlitePool,
+}
+
+impl SqliteRepo {
+    pub async fn connect(cfg: &Config) -> Result<Self, AppError> {
+        use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+
+        let connect_opts = SqliteConnectOptions::from_str(&cfg.database_url).map_err(|_| {
+            AppError::invalid_config(
+                "DATABASE_URL is not a valid SQLite connection string (SqliteConnectOptions parse failed)",
+            )
+        })?;
+
+        let fut = SqlitePoolOptions::new()
+            .max_connections(cfg.pool_max_connections)
+            .min_connections(cfg.pool_min_connections)
+            .acquire_timeout(cfg.db_acquire_timeout)
+            .connect_with(connect_opts);
+
+        let pool = with_connect_timeout(cfg, fut).await?;
+        Ok(Self { pool })
+    }
+
+    /// Pool SQLite en memoria y lazy para tests: no requiere DB real.
+    #[cfg(test)]
+    pub fn in_memory_lazy() -> Self {
+        use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+
+        let connect_opts = SqliteConnectOptions::from_str("sqlite::memory:")
+            .expect("parse SqliteConnectOptions")
+            .shared_cache(true);
+
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .acquire_timeout(Duration::from_secs(1))
+            .connect_lazy_with(connect_opts);
+
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl Repository for SqliteRepo {
+    async fn get_item(&self, id: i32) -> Result<Option<ItemDto>, AppError> {
+        use sqlx::Row;
+
+        let row_opt = sqlx::query(r#"SELECT id, name FROM items WHERE id = ?1"#)
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(AppError::Db)?;
+
+        match row_opt {
+            Some(row) => {
+                let id: i32 = row.try_get("id").map_err(AppError::Db)?;
+                let name: String = row.try_get("name").map_err(AppError::Db)?;
+                Ok(Some(ItemDto { id, name }))
+            }
+            None => Ok(None),
+        }
+    }
+
+    async fn ping(&self) -> Result<(), AppError> {
+        sqlx::query("SELECT 1")
+            .execute(&self.pool)
+            .await
+            .map_err(AppError::Db)?;
+        Ok(())
+    }
+
+    fn pool_stats(&self) -> Option<PoolStats> {
+        Some(PoolStats {
+            size: self.pool.size(),
+            idle: self.pool.num_idle(),
+        })
+    }
+
+    async fn close(&self) {
+        self.pool.close().await;
+    }
+}
+
+pub struct MySqlRepo {
+    pub pool: sqlx::MySqlPool,
+}
+
+impl MySqlRepo {
+    pub async fn connect(cfg: &Config) -> Result<Self, AppError> {
+        use sqlx::mysql::{MySqlConnectOptions, MySqlPoolOptions};
+
+        let connect_opts = MySqlConnectOptions::from_str(&cfg.database_url).map_err(|_| {
+            AppError::invalid_config(
+                "DATABASE_URL is not a valid MySQL connection string (MySqlConnectOptions parse failed)",
+            )
+        })?;
+
+        let fut = MySqlPoolOptions::new()
+            .max_connections(cfg.pool_max_connections)
+            .min_connections(cfg.pool_min_connections)
+            .acquire_timeout(cfg.db_acquire_timeout)
+            .idle_timeout(Duration::from_secs(30))
+            .max_lifetime(Duration::from_secs(300))
+            .connect_with(connect_opts);
+
+        let pool = with_connect_timeout(cfg, fut).await?;
+        Ok(Self { pool })
+    }
+}
+
+#[async_trait]
+impl Repository for MySqlRepo {
+    async fn get_item(&self, id: i32) -> Result<Option<ItemDto>, AppError> {
+        use sqlx::Row;
+
+        let row_opt = sqlx::query(r#"SELECT id, name FROM items WHERE id = ?"#)
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(AppError::Db)?;
+
+        match row_opt {
+            Some(row) => {
+                let id: i32 = row.try_get("id").map_err(AppError::Db)?;
+                let name: String = row.try_get("name").map_err(AppError::Db)?;
+                Ok(Some(ItemDto { id, name }))
+            }
+            None => Ok(None),
+        }
+    }
+
+    async fn ping(&self) -> Result<(), AppError> {
+        sqlx::query("SELECT 1")
+            .execute(&self.pool)
+            .await
+            .map_err(AppError::Db)?;
+        Ok(())
+    }
+
+    fn pool_stats(&self) -> Option<PoolStats> {
+        Some(PoolStats {
+            size: self.pool.size(),
+            idle: self.pool.num_idle(),
+        })
+    }
+
+    async fn close(&self) {
+        self.pool.close().await;
+    }
+}